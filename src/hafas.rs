@@ -0,0 +1,282 @@
+//! Resolving trip information through Deutsche Bahn's HAFAS journey planner (via a public REST
+//! wrapper such as db-rest), for use when a train's on-board portal is unreachable, or when you
+//! are not physically on the train but still want structured trip info.
+
+extern crate hyper;
+extern crate rustc_serialize;
+extern crate time;
+
+use std::io::prelude::Read;
+use hyper::Client;
+use hyper::header::Headers;
+use hyper::status::StatusCode;
+use rustc_serialize::json;
+use time::{Duration, Tm};
+use on_board_info::{OnBoardInfo, OnBoardStop};
+use Error;
+
+/// Parses a HAFAS RFC3339 timestamp.
+fn parse_rfc3339(timestamp: &str) -> Option<Tm> {
+    time::strptime(timestamp, "%Y-%m-%dT%H:%M:%S%z").ok()
+}
+
+/// Percent-encodes a string for safe interpolation into a URL query parameter.
+///
+/// Station names are fuzzy, user-supplied text ("Frankfurt Hbf", "München Hbf") rather than
+/// machine-generated identifiers, so spaces and umlauts are the common case, not the exception.
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+
+    for byte in value.as_bytes() {
+        match *byte {
+            b'A'...b'Z' | b'a'...b'z' | b'0'...b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(*byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+
+    encoded
+}
+
+/// A station or stop location, as resolved by a HAFAS `/locations` query.
+#[derive(RustcDecodable, Debug)]
+pub struct Location {
+    id: String,
+    name: String,
+}
+
+impl Location {
+    /// The EVA number of this location.
+    pub fn eva(&self) -> &str {
+        &self.id
+    }
+
+    /// The human-readable name of this location.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// The line operating a `Leg`, e.g. "ICE 123" or "S 8".
+#[derive(RustcDecodable, Debug)]
+pub struct Line {
+    name: String,
+}
+
+/// A single stopover along a `Leg`.
+#[derive(RustcDecodable, Debug)]
+#[allow(non_snake_case)]
+pub struct Stopover {
+    stop: Location,
+    arrival: Option<String>,
+    plannedArrival: Option<String>,
+    arrivalDelay: Option<i64>,
+    departure: Option<String>,
+    plannedDeparture: Option<String>,
+    departureDelay: Option<i64>,
+}
+
+/// A single leg of a `Journey`, i.e. travel on one train without changing.
+#[derive(RustcDecodable, Debug)]
+pub struct Leg {
+    line: Line,
+    origin: Location,
+    destination: Location,
+    stopovers: Vec<Stopover>,
+}
+
+/// A single journey, as returned by a HAFAS `/journeys` query.
+///
+/// A journey may consist of several `legs` if it requires changing trains; `HafasClient::journeys`
+/// only exposes the first leg as `OnBoardInfo`, matching its on-board counterparts which also
+/// describe a single train rather than a whole itinerary.
+#[derive(RustcDecodable, Debug)]
+pub struct Journey {
+    /// The legs making up this journey, in travel order.
+    pub legs: Vec<Leg>,
+}
+
+#[derive(RustcDecodable, Debug)]
+struct JourneysResponse {
+    journeys: Vec<Journey>,
+}
+
+impl OnBoardStop for Stopover {
+    fn station_eva(&self) -> &str {
+        self.stop.eva()
+    }
+
+    fn station_name(&self) -> &str {
+        self.stop.name()
+    }
+
+    fn scheduled_arrival(&self) -> Option<Tm> {
+        self.plannedArrival.as_ref().and_then(|t| parse_rfc3339(t))
+    }
+
+    fn actual_arrival(&self) -> Option<Tm> {
+        self.arrival.as_ref().and_then(|t| parse_rfc3339(t)).or_else(|| self.scheduled_arrival())
+    }
+
+    fn scheduled_departure(&self) -> Option<Tm> {
+        self.plannedDeparture.as_ref().and_then(|t| parse_rfc3339(t))
+    }
+
+    fn actual_departure(&self) -> Option<Tm> {
+        self.departure.as_ref().and_then(|t| parse_rfc3339(t)).or_else(|| self.scheduled_departure())
+    }
+
+    fn arrival_delay(&self) -> Option<Duration> {
+        self.arrivalDelay.map(Duration::seconds)
+    }
+
+    fn departure_delay(&self) -> Option<Duration> {
+        self.departureDelay.map(Duration::seconds)
+    }
+
+    fn passed(&self) -> bool {
+        // A HAFAS journey is a timetable lookup, not a live on-board position, so there is no
+        // notion of a stop already having been passed.
+        false
+    }
+}
+
+impl OnBoardInfo for Leg {
+    fn train_identifier(&self) -> String {
+        self.line.name.clone()
+    }
+
+    fn stops(&self) -> Vec<&OnBoardStop> {
+        self.stopovers.iter().map(|s| s as &OnBoardStop).collect()
+    }
+
+    fn next_stop(&self) -> Option<&OnBoardStop> {
+        // Not meaningful without a live position; see `Stopover::passed`.
+        None
+    }
+
+    fn previous_stop(&self) -> Option<&OnBoardStop> {
+        None
+    }
+
+    fn origin(&self) -> Option<&OnBoardStop> {
+        self.stopovers.first().map(|s| s as &OnBoardStop)
+    }
+
+    fn destination(&self) -> Option<&OnBoardStop> {
+        self.stopovers.last().map(|s| s as &OnBoardStop)
+    }
+
+    fn total_distance(&self) -> f64 {
+        // Not exposed by the journeys endpoint.
+        0f64
+    }
+}
+
+/// Looks up trip information through a HAFAS REST wrapper, as an alternative to an on-board
+/// portal.
+pub struct HafasClient {
+    base_url: String,
+    user_agent: String,
+}
+
+impl HafasClient {
+    /// Creates a new `HafasClient` pointed at a HAFAS REST wrapper, e.g. "https://v6.db.transport.rest".
+    pub fn new(base_url: &str, user_agent: &str) -> HafasClient {
+        HafasClient {
+            base_url: base_url.to_string(),
+            user_agent: user_agent.to_string(),
+        }
+    }
+
+    /// Requests a page from the HAFAS REST wrapper.
+    fn request(&self, url: &str) -> Result<String, Error> {
+        let http_client = Client::new();
+        let mut headers = Headers::new();
+        headers.set_raw("User-Agent", vec![self.user_agent.as_bytes().to_vec()]);
+
+        let mut response = match http_client.get(url).headers(headers).send() {
+            Ok(response) => response,
+            Err(_) => return Err(Error::Transport),
+        };
+
+        match response.status {
+            StatusCode::Ok => {
+                let mut text = String::new();
+                match response.read_to_string(&mut text) {
+                    Ok(_) => Ok(text),
+                    Err(_) => Err(Error::Transport),
+                }
+            }
+            status => Err(Error::Http(status)),
+        }
+    }
+
+    /// Resolves a fuzzy station name to matching `Location`s via the `/locations` endpoint.
+    pub fn resolve_station(&self, name: &str) -> Option<Vec<Location>> {
+        let url = format!("{}/locations?query={}", self.base_url, percent_encode(name));
+        match self.request(&url) {
+            Ok(response) => json::decode(&response).ok(),
+            Err(_) => None,
+        }
+    }
+
+    /// Finds journeys between two fuzzy station names departing around `when`.
+    ///
+    /// `from` and `to` are resolved to EVA numbers via `resolve_station`, using the first match
+    /// for each. Returns the first leg of every matching journey, boxed as `OnBoardInfo` so
+    /// downstream code is identical to the on-board backends.
+    pub fn journeys(&self, from: &str, to: &str, when: Tm) -> Option<Vec<Box<OnBoardInfo>>> {
+        let from_eva = match self.resolve_station(from) {
+            Some(mut locations) => {
+                if locations.is_empty() {
+                    return None;
+                }
+                locations.remove(0).id
+            }
+            None => return None,
+        };
+        let to_eva = match self.resolve_station(to) {
+            Some(mut locations) => {
+                if locations.is_empty() {
+                    return None;
+                }
+                locations.remove(0).id
+            }
+            None => return None,
+        };
+
+        let departure = match time::strftime("%Y-%m-%dT%H:%M:%S", &when) {
+            Ok(formatted) => formatted,
+            Err(_) => return None,
+        };
+
+        let url = format!("{}/journeys?from={}&to={}&departure={}",
+                           self.base_url,
+                           percent_encode(&from_eva),
+                           percent_encode(&to_eva),
+                           percent_encode(&departure));
+
+        match self.request(&url) {
+            Ok(response) => {
+                match json::decode::<JourneysResponse>(&response) {
+                    Ok(parsed) => {
+                        Some(parsed.journeys
+                            .into_iter()
+                            .filter_map(|mut journey| {
+                                if journey.legs.is_empty() {
+                                    None
+                                } else {
+                                    Some(Box::new(journey.legs.remove(0)) as Box<OnBoardInfo>)
+                                }
+                            })
+                            .collect())
+                    }
+                    Err(_) => None,
+                }
+            }
+            Err(_) => None,
+        }
+    }
+}
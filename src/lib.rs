@@ -7,9 +7,17 @@ extern crate time;
 
 pub mod status;
 pub mod trip_info;
+pub mod on_board_info;
+pub mod zugportal;
+pub mod travelynx;
+pub mod hafas;
+pub mod ts_millis;
 
+use std::error;
+use std::fmt;
 use std::io::prelude::Read;
 use std::str::FromStr;
+use std::time::Duration;
 use hyper::Client;
 use hyper::header::Headers;
 use hyper::status::StatusCode;
@@ -17,6 +25,102 @@ use rustc_serialize::json;
 use rustc_serialize::json::DecoderError;
 use status::Status;
 use trip_info::Trip;
+use on_board_info::{OnBoardInfo, OnBoardStatus};
+use zugportal::Journey;
+use zugportal::Status as ZugportalStatus;
+
+/// The GPS coordinates of a train station.
+#[derive(RustcDecodable, Debug)]
+pub struct Coordinates {
+    /// The latitude of the station
+    pub latitude: f32,
+    /// The longitude of the station
+    pub longitude: f32,
+}
+
+/// The mean radius of the earth, in kilometers, used for great-circle distance calculations.
+const EARTH_RADIUS_KM: f64 = 6371f64;
+
+impl Coordinates {
+    /// The great-circle distance to `other`, in kilometers, computed with the haversine formula.
+    pub fn distance_to(&self, other: &Coordinates) -> f64 {
+        let lat1 = (self.latitude as f64).to_radians();
+        let lat2 = (other.latitude as f64).to_radians();
+        let delta_lat = ((other.latitude - self.latitude) as f64).to_radians();
+        let delta_lon = ((other.longitude - self.longitude) as f64).to_radians();
+
+        let a = (delta_lat / 2f64).sin().powi(2) +
+                lat1.cos() * lat2.cos() * (delta_lon / 2f64).sin().powi(2);
+        let c = 2f64 * a.sqrt().atan2((1f64 - a).sqrt());
+
+        EARTH_RADIUS_KM * c
+    }
+}
+
+/// The on-board portal a `TrainInformation` should decode its trip info from.
+///
+/// Every variant decodes a differently-shaped JSON response into its own concrete type, which
+/// is then handed back from `get_trip_info` as a boxed `OnBoardInfo`.
+#[derive(Debug, Clone, Copy)]
+pub enum Backend {
+    /// The iceportal.de on-board API used by ICE trains.
+    Iceportal,
+    /// The zugportal.de on-board API used by S-Bahn and regional trains.
+    Zugportal,
+}
+
+/// The ways a request to an on-board portal can fail.
+///
+/// Lets callers distinguish "network down" from "the portal rejected us" from "the portal
+/// changed its JSON schema", instead of collapsing every failure into `None`.
+#[derive(Debug)]
+pub enum Error {
+    /// The portal responded with a non-200 HTTP status, e.g. 403 because the user-agent spoof
+    /// was rejected.
+    Http(StatusCode),
+    /// The request could not be completed at all, e.g. the on-board WLAN dropped.
+    Transport,
+    /// The response was not valid JSON, or didn't match the expected schema.
+    Decode(DecoderError),
+}
+
+impl Error {
+    /// Converts a `travelynx::Error` into an `Error`, collapsing travelynx's own HTTP/transport
+    /// distinction onto this crate's identical one.
+    fn from_travelynx(err: travelynx::Error) -> Error {
+        match err {
+            travelynx::Error::Http(status) => Error::Http(status),
+            travelynx::Error::Transport => Error::Transport,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Http(status) => write!(f, "portal responded with {}", status),
+            Error::Transport => write!(f, "request could not be completed"),
+            Error::Decode(ref err) => write!(f, "could not decode response: {}", err),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::Http(_) => "portal responded with a non-200 HTTP status",
+            Error::Transport => "request could not be completed",
+            Error::Decode(_) => "response did not match the expected schema",
+        }
+    }
+
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            Error::Decode(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
 
 /// Stores information which is needed to retrieve a status from the train.
 ///
@@ -25,15 +129,15 @@ use trip_info::Trip;
 /// A simple way to use this struct is the following:
 ///
 /// ```
-/// use db_train_info::TrainInformation;
-/// let info = TrainInformation::new("http://ice.portal2/api1/rs/status", "http://ice.portal2/api1/rs/tripInfo", "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/51.0.2704.63 Safari/537.36");
+/// use db_train_info::{Backend, TrainInformation};
+/// let info = TrainInformation::new("http://ice.portal2/api1/rs/status", "http://ice.portal2/api1/rs/tripInfo", "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/51.0.2704.63 Safari/537.36", Backend::Iceportal, None, None, None);
 /// ```
 ///
 /// You can now use this variable to make calls to the API like this:
 ///
 /// ```
-/// # use db_train_info::TrainInformation;
-/// # let info = TrainInformation::new("https://raw.githubusercontent.com/fceschmidt/db-train-info/master/assets/status.json", "http://ice.portal2/api1/rs/tripInfo", "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/51.0.2704.63 Safari/537.36");
+/// # use db_train_info::{Backend, TrainInformation};
+/// # let info = TrainInformation::new("https://raw.githubusercontent.com/fceschmidt/db-train-info/master/assets/status.json", "http://ice.portal2/api1/rs/tripInfo", "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/51.0.2704.63 Safari/537.36", Backend::Iceportal, None, None, None);
 /// println!("Current train speed: {} km/h", info.get_speed().unwrap());
 /// ```
 pub struct TrainInformation {
@@ -43,15 +147,27 @@ pub struct TrainInformation {
     trip_info_url: String,
     /// The user-agent which should be passed with the HTTP GET requests.
     user_agent: String,
+    /// The on-board portal this `TrainInformation` decodes its trip info from.
+    backend: Backend,
+    /// The timeout given as `connect_timeout` to `new`, if any. It is applied as the HTTP
+    /// client's write timeout; establishing the connection itself is not limited by it.
+    connect_timeout: Option<Duration>,
+    /// The timeout for reading the portal's response, if any.
+    read_timeout: Option<Duration>,
+    /// Extra HTTP headers sent with every request, on top of the spoofed user-agent.
+    extra_headers: Headers,
 }
 
 /// Functions to retrieve information about a train.
 impl TrainInformation {
     /// Creates a new `TrainInformation`.
     ///
-    /// Takes the URL where we can find the JSON encoded `Status` struct, and a user-agent of
-    /// your preferred web browser to spoof a legitimate browser request (otherwise we get
-    /// 403 Forbidden).
+    /// Takes the URL where we can find the JSON encoded `Status` struct, a user-agent of your
+    /// preferred web browser to spoof a legitimate browser request (otherwise we get 403
+    /// Forbidden), the `Backend` whose JSON shape the trip info URL serves, connect/read
+    /// timeouts (`None` for no timeout), and any extra headers to send on top of the spoofed
+    /// user-agent (`None` for none). The connect timeout is applied as the HTTP client's write
+    /// timeout, so it does not limit the time taken to establish the connection.
     ///
     /// # Panics
     /// If converting `status_url` or `user_agent` to `String` fails, this function panics.
@@ -59,40 +175,55 @@ impl TrainInformation {
     /// # Example
     ///
     /// ```
-    /// use db_train_info::TrainInformation;
-    /// let info = TrainInformation::new("http://ice.portal2/api1/rs/status", "http://ice.portal2/api1/rs/tripInfo", "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/51.0.2704.63 Safari/537.36");
+    /// use db_train_info::{Backend, TrainInformation};
+    /// let info = TrainInformation::new("http://ice.portal2/api1/rs/status", "http://ice.portal2/api1/rs/tripInfo", "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/51.0.2704.63 Safari/537.36", Backend::Iceportal, None, None, None);
     /// ```
-    pub fn new(status_url: &str, trip_info_url: &str, user_agent: &str) -> TrainInformation {
+    pub fn new(status_url: &str,
+               trip_info_url: &str,
+               user_agent: &str,
+               backend: Backend,
+               connect_timeout: Option<Duration>,
+               read_timeout: Option<Duration>,
+               extra_headers: Option<Headers>)
+               -> TrainInformation {
         TrainInformation {
             status_url: String::from_str(status_url).unwrap(),
             trip_info_url: String::from_str(trip_info_url).unwrap(),
             user_agent: String::from_str(user_agent).unwrap(),
+            backend: backend,
+            connect_timeout: connect_timeout,
+            read_timeout: read_timeout,
+            extra_headers: extra_headers.unwrap_or_else(Headers::new),
         }
     }
 
     /// Requests a page from the ICE portal API.
     ///
-    /// On success, a string containing the server response is returned, otherwise a HTTP
-    /// status code from the `hyper::status::StatusCode` enum.
-    ///
-    /// # Panics
-    /// If the request does not return a HTTP response, this function panics.
-    pub fn request(&self, url: &str) -> Result<String, StatusCode> {
-        let http_client = Client::new();
+    /// On success, a string containing the server response is returned, otherwise an `Error`
+    /// describing what went wrong.
+    pub fn request(&self, url: &str) -> Result<String, Error> {
+        let mut http_client = Client::new();
+        http_client.set_read_timeout(self.read_timeout);
+        http_client.set_write_timeout(self.connect_timeout);
 
         // This dirty user-agent trick got everything to work, eh :)
-        let mut http_headers = Headers::new();
+        let mut http_headers = self.extra_headers.clone();
         http_headers.set_raw("User-Agent", vec![self.user_agent.as_bytes().to_vec()]);
 
-        let mut response = http_client.get(url).headers(http_headers).send().unwrap();
+        let mut response = match http_client.get(url).headers(http_headers).send() {
+            Ok(response) => response,
+            Err(_) => return Err(Error::Transport),
+        };
 
         match response.status {
             StatusCode::Ok => {
                 let mut text = String::new();
-                response.read_to_string(&mut text).unwrap();
-                Ok(text)
+                match response.read_to_string(&mut text) {
+                    Ok(_) => Ok(text),
+                    Err(_) => Err(Error::Transport),
+                }
             }
-            status => Err(status),
+            status => Err(Error::Http(status)),
         }
     }
 
@@ -104,8 +235,8 @@ impl TrainInformation {
         return json::decode(&response);
     }
 
-    /// Generates a `TripInfo` containing information about the train from the result of a
-    /// tripInfo request.
+    /// Generates a `TripInfo` containing information about the train from the result of an
+    /// iceportal.de tripInfo request.
     ///
     /// On success, returns an `Ok` with trip information, otherwise an `Err` with the decoder
     /// error.
@@ -113,37 +244,141 @@ impl TrainInformation {
         return json::decode(&response);
     }
 
+    /// Generates a `Journey` containing information about the train from the result of a
+    /// zugportal.de journey request.
+    ///
+    /// On success, returns an `Ok` with journey information, otherwise an `Err` with the
+    /// decoder error.
+    pub fn deserialize_journey(response: String) -> Result<Journey, DecoderError> {
+        return json::decode(&response);
+    }
+
+    /// Generates a `zugportal::Status` containing information about the train from the result
+    /// of a zugportal.de status request.
+    ///
+    /// On success, returns an `Ok` with the status, otherwise an `Err` with the decoder error.
+    pub fn deserialize_zugportal_status(response: String) -> Result<ZugportalStatus, DecoderError> {
+        return json::decode(&response);
+    }
+
     /// Convenience function to get the current status of the train.
-    pub fn get_status(&self) -> Option<Status> {
+    ///
+    /// The result is decoded according to this `TrainInformation`'s `Backend` and handed back
+    /// as a boxed `OnBoardStatus`, so callers don't need to know which portal answered.
+    pub fn get_status(&self) -> Result<Box<OnBoardStatus>, Error> {
         match self.request(&self.status_url) {
             Ok(response) => {
-                match TrainInformation::deserialize_status(response) {
-                    Ok(status) => Some(status),
-                    Err(_) => None,
+                match self.backend {
+                    Backend::Iceportal => {
+                        TrainInformation::deserialize_status(response)
+                            .map(|status| Box::new(status) as Box<OnBoardStatus>)
+                            .map_err(Error::Decode)
+                    }
+                    Backend::Zugportal => {
+                        TrainInformation::deserialize_zugportal_status(response)
+                            .map(|status| Box::new(status) as Box<OnBoardStatus>)
+                            .map_err(Error::Decode)
+                    }
                 }
             }
-            Err(_) => None,
+            Err(err) => Err(err),
         }
     }
 
     /// Convenience function to get the current trip information of the train.
-    pub fn get_trip_info(&self) -> Option<Trip> {
+    ///
+    /// The result is decoded according to this `TrainInformation`'s `Backend` and handed back
+    /// as a boxed `OnBoardInfo`, so callers don't need to know which portal answered.
+    pub fn get_trip_info(&self) -> Result<Box<OnBoardInfo>, Error> {
         match self.request(&self.trip_info_url) {
             Ok(response) => {
-                match TrainInformation::deserialize_trip_info(response) {
-                    Ok(status) => Some(status),
-                    Err(_) => None,
+                match self.backend {
+                    Backend::Iceportal => {
+                        TrainInformation::deserialize_trip_info(response)
+                            .map(|trip| Box::new(trip) as Box<OnBoardInfo>)
+                            .map_err(Error::Decode)
+                    }
+                    Backend::Zugportal => {
+                        TrainInformation::deserialize_journey(response)
+                            .map(|journey| Box::new(journey) as Box<OnBoardInfo>)
+                            .map_err(Error::Decode)
+                    }
                 }
             }
-            Err(_) => None,
+            Err(err) => Err(err),
         }
     }
 
     /// Convenience function to get the current speed of the train.
-    pub fn get_speed(&self) -> Option<f32> {
-        match self.get_status() {
-            Some(status) => Some(status.speed),
-            None => None,
+    pub fn get_speed(&self) -> Result<f32, Error> {
+        self.get_status().map(|status| status.speed())
+    }
+
+    /// Fetches this train's current trip info and checks it in to a travelynx instance.
+    pub fn checkin(&self, travelynx_url: &str, token: &str) -> Result<(), Error> {
+        match self.get_trip_info() {
+            Ok(trip) => {
+                travelynx::checkin(travelynx_url, token, &*trip).map_err(Error::from_travelynx)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Checks out of the current check-in on a travelynx instance.
+    pub fn checkout(&self, travelynx_url: &str, token: &str) -> Result<(), Error> {
+        travelynx::checkout(travelynx_url, token).map_err(Error::from_travelynx)
+    }
+
+    /// Looks up HAFAS journeys between two fuzzy station names via a HAFAS REST wrapper.
+    ///
+    /// A thin wrapper around `hafas::HafasClient::journeys`, built with this
+    /// `TrainInformation`'s own user-agent, so a caller who already has a `TrainInformation`
+    /// doesn't need to separately construct a `HafasClient`.
+    pub fn journey(&self,
+                    hafas_base_url: &str,
+                    from: &str,
+                    to: &str,
+                    when: time::Tm)
+                    -> Option<Vec<Box<OnBoardInfo>>> {
+        hafas::HafasClient::new(hafas_base_url, &self.user_agent).journeys(from, to, when)
+    }
+
+    /// Whether this `TrainInformation`'s trip info endpoint currently responds with HTTP 200.
+    ///
+    /// Useful to probe a portal before committing to it, e.g. in `choose_api`.
+    pub fn is_available(&self) -> bool {
+        self.request(&self.trip_info_url).is_ok()
+    }
+
+    /// Probes iceportal.de, then zugportal.de, and returns a `TrainInformation` already
+    /// configured for whichever on-board portal responds first.
+    ///
+    /// Saves callers who don't know in advance whether they're on an ICE or an S-Bahn/regional
+    /// train from having to pick a `Backend` themselves. Returns `None` if neither portal's
+    /// trip info endpoint is reachable.
+    pub fn choose_api(user_agent: &str) -> Option<TrainInformation> {
+        let candidates = [(ICEPORTAL_STATUS_URL, ICEPORTAL_TRIP_INFO_URL, Backend::Iceportal),
+                           (ZUGPORTAL_STATUS_URL, ZUGPORTAL_TRIP_INFO_URL, Backend::Zugportal)];
+
+        for &(status_url, trip_info_url, backend) in candidates.iter() {
+            let info = TrainInformation::new(status_url, trip_info_url, user_agent, backend,
+                                              None, None, None);
+            if info.is_available() {
+                return Some(info);
+            }
         }
+
+        None
     }
 }
+
+/// The well-known status endpoint of the iceportal.de on-board API.
+const ICEPORTAL_STATUS_URL: &'static str = "https://iceportal.de/api1/rs/status";
+/// The well-known tripInfo endpoint of the iceportal.de on-board API.
+const ICEPORTAL_TRIP_INFO_URL: &'static str = "https://iceportal.de/api1/rs/tripInfo";
+/// The well-known status endpoint of the zugportal.de on-board API.
+const ZUGPORTAL_STATUS_URL: &'static str = "https://zugportal.de/@prd/zupo-travel-information/\
+                                             api/status";
+/// The well-known journey endpoint of the zugportal.de on-board API.
+const ZUGPORTAL_TRIP_INFO_URL: &'static str = "https://zugportal.de/@prd/zupo-travel-information/\
+                                                api/journey";
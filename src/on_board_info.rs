@@ -0,0 +1,178 @@
+//! A provider-neutral view of a decoded trip.
+
+extern crate time;
+
+use time::{Duration, Tm};
+use trip_info::{Stop, Trip};
+use status::Status;
+use Coordinates;
+
+/// The portal-neutral view of a single stop along a trip.
+///
+/// Every on-board portal describes a stop differently, so each portal's own stop type
+/// implements this trait instead of being forced into the ICE-specific `Stop`/`TimeInfo` shape.
+pub trait OnBoardStop {
+    /// The EVA number identifying this stop's station.
+    fn station_eva(&self) -> &str;
+
+    /// The human-readable name of this stop's station.
+    fn station_name(&self) -> &str;
+
+    /// The scheduled time of arrival, if any.
+    fn scheduled_arrival(&self) -> Option<Tm>;
+
+    /// The actual (possibly delayed) time of arrival, if any.
+    fn actual_arrival(&self) -> Option<Tm>;
+
+    /// The scheduled time of departure, if any.
+    fn scheduled_departure(&self) -> Option<Tm>;
+
+    /// The actual (possibly delayed) time of departure, if any.
+    fn actual_departure(&self) -> Option<Tm>;
+
+    /// The arrival delay, if both a scheduled and an actual arrival time are known.
+    fn arrival_delay(&self) -> Option<Duration>;
+
+    /// The departure delay, if both a scheduled and an actual departure time are known.
+    fn departure_delay(&self) -> Option<Duration>;
+
+    /// Whether this stop has already been passed.
+    fn passed(&self) -> bool;
+}
+
+/// The portal-neutral operations that any on-board information source can answer.
+///
+/// Each on-board portal (iceportal.de, zugportal.de, ...) decodes its own JSON shape into its
+/// own concrete type, then implements this trait so callers can write code once against it
+/// instead of against the ICE-specific `Trip`/`Stop`/`StationInfo` structs.
+pub trait OnBoardInfo {
+    /// Returns the identifier of the train, e.g. "ICE 123" or "S 8".
+    fn train_identifier(&self) -> String;
+
+    /// The stops along the trajectory of this train, in order.
+    fn stops(&self) -> Vec<&OnBoardStop>;
+
+    /// A reference to the next stop in the trajectory of the train.
+    fn next_stop(&self) -> Option<&OnBoardStop>;
+
+    /// A reference to the previous stop in the trajectory of the train.
+    fn previous_stop(&self) -> Option<&OnBoardStop>;
+
+    /// A reference to the first stop in the trajectory.
+    fn origin(&self) -> Option<&OnBoardStop>;
+
+    /// A reference to the final stop in the trajectory.
+    fn destination(&self) -> Option<&OnBoardStop>;
+
+    /// The total distance travelled by this train from start to end, in kilometers.
+    fn total_distance(&self) -> f64;
+}
+
+/// The ICE portal's `Stop` is one implementation of `OnBoardStop`.
+impl OnBoardStop for Stop {
+    fn station_eva(&self) -> &str {
+        self.station().eva_nr()
+    }
+
+    fn station_name(&self) -> &str {
+        self.station().name()
+    }
+
+    fn scheduled_arrival(&self) -> Option<Tm> {
+        self.timetable().scheduled_arrival()
+    }
+
+    fn actual_arrival(&self) -> Option<Tm> {
+        self.timetable().actual_arrival()
+    }
+
+    fn scheduled_departure(&self) -> Option<Tm> {
+        self.timetable().scheduled_departure()
+    }
+
+    fn actual_departure(&self) -> Option<Tm> {
+        self.timetable().actual_departure()
+    }
+
+    fn arrival_delay(&self) -> Option<Duration> {
+        if self.timetable().scheduled_arrival().is_some() && self.timetable().actual_arrival().is_some() {
+            Some(self.timetable().arrival_delay())
+        } else {
+            None
+        }
+    }
+
+    fn departure_delay(&self) -> Option<Duration> {
+        if self.timetable().scheduled_departure().is_some() && self.timetable().actual_departure().is_some() {
+            Some(self.timetable().departure_delay())
+        } else {
+            None
+        }
+    }
+
+    fn passed(&self) -> bool {
+        self.info().passed()
+    }
+}
+
+/// The iceportal.de decoding of a trip is one implementation of `OnBoardInfo`.
+impl OnBoardInfo for Trip {
+    fn train_identifier(&self) -> String {
+        Trip::train_identifier(self)
+    }
+
+    fn stops(&self) -> Vec<&OnBoardStop> {
+        self.stops.iter().map(|s| s as &OnBoardStop).collect()
+    }
+
+    fn next_stop(&self) -> Option<&OnBoardStop> {
+        Trip::next_stop(self).map(|s| s as &OnBoardStop)
+    }
+
+    fn previous_stop(&self) -> Option<&OnBoardStop> {
+        Trip::previous_stop(self).map(|s| s as &OnBoardStop)
+    }
+
+    fn origin(&self) -> Option<&OnBoardStop> {
+        Trip::origin(self).map(|s| s as &OnBoardStop)
+    }
+
+    fn destination(&self) -> Option<&OnBoardStop> {
+        Trip::destination(self).map(|s| s as &OnBoardStop)
+    }
+
+    fn total_distance(&self) -> f64 {
+        Trip::total_distance(self)
+    }
+}
+
+/// The portal-neutral live status of a train.
+///
+/// Every on-board portal reports the same handful of facts (speed, position, server time), but
+/// decodes them from its own JSON shape, so each portal's own status type implements this trait
+/// instead of being forced into the ICE-specific `status::Status`.
+pub trait OnBoardStatus {
+    /// The current speed of the train, in km/h.
+    fn speed(&self) -> f32;
+
+    /// The current GPS coordinates of the train.
+    fn coordinates(&self) -> Coordinates;
+
+    /// The server time the status was reported at, if it could be parsed.
+    fn server_time(&self) -> Option<Tm>;
+}
+
+/// The ICE portal's `Status` is one implementation of `OnBoardStatus`.
+impl OnBoardStatus for Status {
+    fn speed(&self) -> f32 {
+        Status::speed(self)
+    }
+
+    fn coordinates(&self) -> Coordinates {
+        Status::coordinates(self)
+    }
+
+    fn server_time(&self) -> Option<Tm> {
+        Some(Status::server_time(self))
+    }
+}
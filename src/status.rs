@@ -1,8 +1,8 @@
 extern crate time;
 
 use std::fmt;
-use time::Timespec;
 use super::Coordinates;
+use ts_millis::TsMillis;
 
 /// The current state of the train (Speed, Location, Time).
 ///
@@ -18,7 +18,7 @@ pub struct Status {
     /// The GPS longitude of the train.
     longitude: f32,
     /// The server time of the request.
-    serverTime: i64,
+    serverTime: TsMillis,
 }
 
 impl Status {
@@ -37,11 +37,7 @@ impl Status {
 
     /// Get the server time.
     pub fn server_time(&self) -> time::Tm {
-        let timestamp = Timespec {
-            sec: self.serverTime / 1000,
-            nsec: (self.serverTime % 1000) as i32 * 1_000_000
-        };
-        time::at(timestamp)
+        self.serverTime.tm()
     }
 }
 
@@ -52,12 +48,7 @@ impl fmt::Display for Status {
     /// Prints speed, GPS coordinates and the server timestamp interpreted as local time. If
     /// formatting the server timestamp fails, it is omitted in the result of this function.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        // Calculate the timestamp for the `time` crate
-        let timestamp = Timespec {
-            sec: self.serverTime / 1000,
-            nsec: (self.serverTime % 1000) as i32 * 1000000,
-        };
-        let tm = time::at(timestamp);
+        let tm = self.serverTime.tm();
         let result = time::strftime("%H:%M:%S", &tm);
 
         // Check whether conversion went OK or we encountered an error, and either print the
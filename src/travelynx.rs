@@ -0,0 +1,221 @@
+//! Support for checking a decoded trip in to [travelynx](https://travelynx.de), a service for
+//! logging train journeys.
+
+extern crate hyper;
+extern crate rustc_serialize;
+
+use std::error;
+use std::fmt;
+use hyper::Client;
+use hyper::header::{ContentType, Headers};
+use hyper::status::StatusCode;
+use rustc_serialize::Encodable;
+use rustc_serialize::json;
+use on_board_info::OnBoardInfo;
+use trip_info::StationInfo;
+
+/// The ways a request to travelynx can fail.
+#[derive(Debug)]
+pub enum Error {
+    /// travelynx responded with a non-200 HTTP status.
+    Http(StatusCode),
+    /// The request could not be completed at all, e.g. the on-board WLAN dropped.
+    Transport,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Http(status) => write!(f, "travelynx responded with {}", status),
+            Error::Transport => write!(f, "request could not be completed"),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::Http(_) => "travelynx responded with a non-200 HTTP status",
+            Error::Transport => "request could not be completed",
+        }
+    }
+}
+
+/// A train's type and line/number, e.g. "ICE" and "123", as travelynx expects them.
+pub struct TrainReference {
+    train_type: String,
+    number: String,
+}
+
+impl TrainReference {
+    /// Derives a `TrainReference` from a decoded trip's `train_identifier()`, e.g. "ICE 123",
+    /// by splitting off the trailing whitespace-delimited token as the number.
+    ///
+    /// The train type isn't necessarily a single word: `zugportal::Journey::train_identifier()`
+    /// returns identifiers like "S 8 12345", where "S 8" is the line label and "12345" the
+    /// train number. Splitting on the *first* space instead would misparse those as type "S",
+    /// number "8 12345".
+    pub fn from_trip(trip: &OnBoardInfo) -> TrainReference {
+        let identifier = trip.train_identifier();
+        let mut tokens: Vec<&str> = identifier.split_whitespace().collect();
+        let number = tokens.pop().unwrap_or("").to_string();
+
+        TrainReference {
+            train_type: tokens.join(" "),
+            number: number,
+        }
+    }
+
+    /// The train's type, e.g. "ICE".
+    pub fn train_type(&self) -> &str {
+        &self.train_type
+    }
+
+    /// The train's line/number, e.g. "123".
+    pub fn number(&self) -> &str {
+        &self.number
+    }
+}
+
+/// The body POSTed to the travelynx check-in endpoint.
+#[derive(RustcEncodable, Debug)]
+struct CheckinRequest<'a> {
+    token: &'a str,
+    train_type: &'a str,
+    vzn: &'a str,
+    from_eva: &'a str,
+    to_eva: &'a str,
+}
+
+/// The body POSTed to the travelynx check-out endpoint.
+#[derive(RustcEncodable, Debug)]
+struct CheckoutRequest<'a> {
+    token: &'a str,
+}
+
+/// POSTs a JSON-encoded body to a travelynx endpoint below `base_url`.
+fn post<T: Encodable>(base_url: &str, path: &str, body: &T) -> Result<(), Error> {
+    let http_client = Client::new();
+    let mut headers = Headers::new();
+    headers.set(ContentType::json());
+
+    let url = format!("{}{}", base_url, path);
+    let encoded = match json::encode(body) {
+        Ok(encoded) => encoded,
+        Err(_) => return Err(Error::Transport),
+    };
+
+    let response = match http_client.post(&url).headers(headers).body(&encoded).send() {
+        Ok(response) => response,
+        Err(_) => return Err(Error::Transport),
+    };
+
+    match response.status {
+        StatusCode::Ok => Ok(()),
+        status => Err(Error::Http(status)),
+    }
+}
+
+/// Checks a decoded trip in to travelynx.
+///
+/// The train reference is derived from `train_identifier()` via `TrainReference::from_trip`, and
+/// the departure/destination EVA numbers are taken from the previous and next stop.
+pub fn checkin(base_url: &str, token: &str, trip: &OnBoardInfo) -> Result<(), Error> {
+    let reference = TrainReference::from_trip(trip);
+    let from_eva = trip.previous_stop().map_or(String::new(), |stop| stop.station_eva().to_string());
+    let to_eva = trip.next_stop().map_or(String::new(), |stop| stop.station_eva().to_string());
+
+    let body = CheckinRequest {
+        token: token,
+        train_type: reference.train_type(),
+        vzn: reference.number(),
+        from_eva: &from_eva,
+        to_eva: &to_eva,
+    };
+
+    post(base_url, "/api/v1/travel/checkin", &body)
+}
+
+/// Checks in to travelynx given an explicit boarding and alighting station, rather than
+/// inferring them from a trip's previous/next stop.
+///
+/// Lets a caller pick any two stops out of a decoded `Trip`'s stop list (via `Stop::station()`)
+/// as the origin and destination, e.g. to record a journey retroactively or to check in before
+/// the portal itself considers the next stop reached.
+pub fn checkin_stations(base_url: &str,
+                         token: &str,
+                         reference: &TrainReference,
+                         origin: &StationInfo,
+                         destination: &StationInfo)
+                         -> Result<(), Error> {
+    let body = CheckinRequest {
+        token: token,
+        train_type: reference.train_type(),
+        vzn: reference.number(),
+        from_eva: origin.eva_nr(),
+        to_eva: destination.eva_nr(),
+    };
+
+    post(base_url, "/api/v1/travel/checkin", &body)
+}
+
+/// Checks out of the current check-in on travelynx.
+pub fn checkout(base_url: &str, token: &str) -> Result<(), Error> {
+    let body = CheckoutRequest { token: token };
+    post(base_url, "/api/v1/travel/checkout", &body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TrainReference;
+    use on_board_info::{OnBoardInfo, OnBoardStop};
+
+    /// A stub `OnBoardInfo` whose only purpose is to hand back a fixed `train_identifier()`.
+    struct FakeTrip(&'static str);
+
+    impl OnBoardInfo for FakeTrip {
+        fn train_identifier(&self) -> String {
+            self.0.to_string()
+        }
+
+        fn stops(&self) -> Vec<&OnBoardStop> {
+            Vec::new()
+        }
+
+        fn next_stop(&self) -> Option<&OnBoardStop> {
+            None
+        }
+
+        fn previous_stop(&self) -> Option<&OnBoardStop> {
+            None
+        }
+
+        fn origin(&self) -> Option<&OnBoardStop> {
+            None
+        }
+
+        fn destination(&self) -> Option<&OnBoardStop> {
+            None
+        }
+
+        fn total_distance(&self) -> f64 {
+            0f64
+        }
+    }
+
+    #[test]
+    fn splits_single_word_train_type() {
+        let reference = TrainReference::from_trip(&FakeTrip("ICE 123"));
+        assert_eq!(reference.train_type(), "ICE");
+        assert_eq!(reference.number(), "123");
+    }
+
+    #[test]
+    fn splits_multi_word_train_type() {
+        // zugportal.de line labels like "S 8" already contain a space, so the train number
+        // must be split off the end, not the first whitespace.
+        let reference = TrainReference::from_trip(&FakeTrip("S 8 12345"));
+        assert_eq!(reference.train_type(), "S 8");
+        assert_eq!(reference.number(), "12345");
+    }
+}
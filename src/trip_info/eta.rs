@@ -0,0 +1,74 @@
+extern crate time;
+
+use super::Stop;
+use super::Trip;
+use time::{Duration, Timespec, Tm};
+
+/// Below this speed (km/h) the train is considered stopped, and the ETA falls back to the
+/// scheduled time plus its delay rather than a motion-based estimate.
+const MIN_MOVING_SPEED: f32 = 3.0;
+
+impl Trip {
+    /// The distance travelled from the origin to the train's current position, in kilometers.
+    fn current_distance(&self) -> f64 {
+        let distance_to_last_stop = self.previous_stop()
+            .map_or(0f64, |stop| stop.info().distance_to_origin());
+        distance_to_last_stop + self.distance_to_previous_stop()
+    }
+
+    /// Predicts the arrival time at `stop`, given the train's current speed in km/h.
+    ///
+    /// Combines a motion-based estimate (remaining distance over speed) with the scheduled
+    /// arrival time plus its delay, and returns whichever is later, so a stopped train doesn't
+    /// report an impossibly early arrival. Returns `None` if `stop` has already been passed, or
+    /// has no scheduled arrival time (such as the origin).
+    pub fn eta(&self, stop: &Stop, speed: f32) -> Option<Tm> {
+        if stop.info().passed() {
+            return None;
+        }
+
+        let scheduled = match stop.timetable().scheduled_arrival() {
+            Some(tm) => tm,
+            None => return None,
+        };
+        let scheduled_estimate: Timespec = scheduled.to_timespec() +
+                                            stop.timetable().arrival_delay();
+
+        let estimate = if speed > MIN_MOVING_SPEED {
+            let remaining_km = stop.info().distance_to_origin() - self.current_distance();
+            let remaining_seconds = remaining_km / (speed as f64) * 3600f64;
+            let motion_estimate = time::now().to_timespec() +
+                                   Duration::seconds(remaining_seconds as i64);
+
+            if motion_estimate > scheduled_estimate {
+                motion_estimate
+            } else {
+                scheduled_estimate
+            }
+        } else {
+            scheduled_estimate
+        };
+
+        Some(time::at(estimate))
+    }
+
+    /// Predicts the arrival time at the final stop of the trajectory, given the train's current
+    /// speed in km/h.
+    ///
+    /// Returns `None` if the destination has already been passed, or could not be determined.
+    pub fn eta_to_destination(&self, speed: f32) -> Option<Tm> {
+        match self.destination() {
+            Some(stop) => self.eta(stop, speed),
+            None => None,
+        }
+    }
+
+    /// Predicts the arrival time at every not-yet-passed stop, given the train's current speed
+    /// in km/h.
+    pub fn eta_for_remaining_stops(&self, speed: f32) -> Vec<(&Stop, Tm)> {
+        self.stops
+            .iter()
+            .filter_map(|stop| self.eta(stop, speed).map(|eta| (stop, eta)))
+            .collect()
+    }
+}
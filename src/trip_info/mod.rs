@@ -1,4 +1,5 @@
 use super::Coordinates;
+use ts_millis::TsMillis;
 
 pub mod train_vicinity;
 pub mod station_info;
@@ -7,6 +8,8 @@ pub mod track_info;
 pub mod misc_info;
 pub mod stop;
 pub mod trip;
+pub mod eta;
+pub mod progress;
 
 /// References the last and the next stop of the train.
 #[derive(RustcDecodable, Debug)]
@@ -33,11 +36,11 @@ pub struct StationInfo {
 #[derive(RustcDecodable, Debug)]
 #[allow(non_snake_case)]
 pub struct TimeInfo {
-    scheduledArrivalTime: Option<i64>,
-    actualArrivalTime: Option<i64>,
+    scheduledArrivalTime: Option<TsMillis>,
+    actualArrivalTime: Option<TsMillis>,
     arrivalDelay: String,
-    scheduledDepartureTime: Option<i64>,
-    actualDepartureTime: Option<i64>,
+    scheduledDepartureTime: Option<TsMillis>,
+    actualDepartureTime: Option<TsMillis>,
     departureDelay: String,
 }
 
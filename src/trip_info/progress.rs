@@ -0,0 +1,80 @@
+use std::cmp::Ordering;
+use super::Stop;
+use super::Trip;
+use status::Status;
+
+/// Below this speed (km/h) an ETA based on the current speed is not meaningful, and `progress`
+/// reports no estimate at all rather than an absurdly long or divide-by-near-zero one.
+const MIN_MOVING_SPEED: f32 = 3.0;
+
+/// The train's progress toward the nearest upcoming stop, estimated from its current GPS fix.
+pub struct Progress<'a> {
+    next_stop: &'a Stop,
+    distance_remaining: f64,
+    eta_minutes: Option<f64>,
+}
+
+impl<'a> Progress<'a> {
+    /// The nearest stop along the trajectory that hasn't been passed yet.
+    pub fn next_stop(&self) -> &Stop {
+        self.next_stop
+    }
+
+    /// The great-circle distance remaining to `next_stop`, in kilometers.
+    pub fn distance_remaining(&self) -> f64 {
+        self.distance_remaining
+    }
+
+    /// The estimated time remaining to `next_stop`, in minutes, based on the current speed.
+    ///
+    /// `None` if the train is moving too slowly (or not at all) for an ETA to be meaningful,
+    /// e.g. while stopped at or approaching a station.
+    pub fn eta_minutes(&self) -> Option<f64> {
+        self.eta_minutes
+    }
+}
+
+impl Trip {
+    /// Estimates progress toward the nearest not-yet-passed stop from a GPS `Status`, using
+    /// haversine great-circle distance rather than the trajectory's own distance-from-start
+    /// bookkeeping.
+    ///
+    /// Picks the closest not-yet-passed stop rather than assuming `next_stop()` is necessarily
+    /// nearest, since a GPS fix can place the train closer to a station further down the line
+    /// than to the one iceportal.de currently considers "next". Returns `None` only if every
+    /// stop has already been passed; `next_stop`/`distance_remaining` don't depend on speed, so
+    /// they're still reported while stopped or slow-moving, with `eta_minutes` omitted instead.
+    pub fn progress(&self, status: &Status) -> Option<Progress> {
+        let position = status.coordinates();
+
+        let nearest = self.stops
+            .iter()
+            .filter(|stop| !stop.info().passed())
+            .min_by(|a, b| {
+                let distance_a = position.distance_to(a.station().coordinates());
+                let distance_b = position.distance_to(b.station().coordinates());
+                // partial_cmp can return None for NaN, which haversine can produce from
+                // floating-point rounding on near-antipodal coordinates; treat that as a tie
+                // rather than panicking.
+                distance_a.partial_cmp(&distance_b).unwrap_or(Ordering::Equal)
+            });
+
+        let stop = match nearest {
+            Some(stop) => stop,
+            None => return None,
+        };
+
+        let distance_remaining = position.distance_to(stop.station().coordinates());
+        let eta_minutes = if status.speed() < MIN_MOVING_SPEED {
+            None
+        } else {
+            Some(distance_remaining / (status.speed() as f64) * 60f64)
+        };
+
+        Some(Progress {
+            next_stop: stop,
+            distance_remaining: distance_remaining,
+            eta_minutes: eta_minutes,
+        })
+    }
+}
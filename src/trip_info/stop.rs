@@ -1,9 +1,12 @@
+extern crate time;
+
 use super::Stop;
 use super::StationInfo;
 use super::TimeInfo;
 use super::TrackInfo;
 use super::MiscInfo;
 use super::DelayReason;
+use time::{Duration, Tm};
 
 impl Stop {
     /// The train station associated with this `Stop`.
@@ -16,6 +19,48 @@ impl Stop {
         &self.timetable
     }
 
+    /// The scheduled time of arrival, if any.
+    pub fn scheduled_arrival(&self) -> Option<Tm> {
+        self.timetable.scheduled_arrival()
+    }
+
+    /// The actual (possibly delayed) time of arrival, if any.
+    pub fn actual_arrival(&self) -> Option<Tm> {
+        self.timetable.actual_arrival()
+    }
+
+    /// The scheduled time of departure, if any.
+    pub fn scheduled_departure(&self) -> Option<Tm> {
+        self.timetable.scheduled_departure()
+    }
+
+    /// The actual (possibly delayed) time of departure, if any.
+    pub fn actual_departure(&self) -> Option<Tm> {
+        self.timetable.actual_departure()
+    }
+
+    /// The delay at this stop, computed as the actual time minus the scheduled one.
+    ///
+    /// Prefers the departure delay, since it's the more relevant one while still at the stop,
+    /// falling back to the arrival delay. `None` unless both the scheduled and the actual time
+    /// are known for the departure or for the arrival.
+    ///
+    /// This is an independent estimate from `timetable().arrival_delay()`/`departure_delay()`,
+    /// which instead parse iceportal's own separate `+N`/`-N` delay string; the two sources can
+    /// disagree, since the delay string is only minute-granularity while this is an exact
+    /// timestamp difference. Don't mix the two for the same stop.
+    pub fn delay(&self) -> Option<Duration> {
+        match (self.scheduled_departure(), self.actual_departure()) {
+            (Some(scheduled), Some(actual)) => Some(actual - scheduled),
+            _ => {
+                match (self.scheduled_arrival(), self.actual_arrival()) {
+                    (Some(scheduled), Some(actual)) => Some(actual - scheduled),
+                    _ => None,
+                }
+            }
+        }
+    }
+
     /// The track on which the train stops at this `Stop`'s station.
     pub fn track(&self) -> &TrackInfo {
         &self.track
@@ -2,58 +2,36 @@ extern crate time;
 
 use super::TimeInfo;
 use time::Duration;
-use time::Timespec;
 use time::Tm;
 use std::str;
 
 impl TimeInfo {
-    /// Convert a Deutsche Bahn timestamp to local time
-    fn timestamp_to_tm(timestamp: i64) -> Tm {
-        let spec = Timespec {
-            sec: timestamp / 1000,
-            nsec: (timestamp % 1000) as i32 * 1_000_000
-        };
-        time::at(spec)
-    }
-
     /// The scheduled time of arrival
     ///
     /// Optional since some stops do not have an arrival time (such as the very first one).
     pub fn scheduled_arrival(&self) -> Option<Tm> {
-        match self.scheduledArrivalTime {
-            Some(timestamp) => Some(TimeInfo::timestamp_to_tm(timestamp)),
-            None => None,
-        }
+        self.scheduledArrivalTime.map(|ts| ts.tm())
     }
 
     /// The scheduled time of departure
     ///
     /// Optional since some stops do not have a departure time (such as the very last one).
     pub fn scheduled_departure(&self) -> Option<Tm> {
-        match self.scheduledDepartureTime {
-            Some(timestamp) => Some(TimeInfo::timestamp_to_tm(timestamp)),
-            None => None,
-        }
+        self.scheduledDepartureTime.map(|ts| ts.tm())
     }
 
     /// The actual time of arrival
     ///
     /// Optional since some stops do not have an arrival time (such as the very first one).
     pub fn actual_arrival(&self) -> Option<Tm> {
-        match self.actualArrivalTime {
-            Some(timestamp) => Some(TimeInfo::timestamp_to_tm(timestamp)),
-            None => None,
-        }
+        self.actualArrivalTime.map(|ts| ts.tm())
     }
 
     /// The actual time of departure
     ///
     /// Optional since some stops do not have a departure time (such as the very last one).
     pub fn actual_departure(&self) -> Option<Tm> {
-        match self.actualDepartureTime {
-            Some(timestamp) => Some(TimeInfo::timestamp_to_tm(timestamp)),
-            None => None,
-        }
+        self.actualDepartureTime.map(|ts| ts.tm())
     }
 
     /// Convert a delay string to a `Duration`
@@ -70,11 +48,21 @@ impl TimeInfo {
     }
 
     /// The arrival delay at a stop
+    ///
+    /// Parsed from iceportal's own `+N`/`-N` delay string, which is only minute-granularity.
+    /// `Stop::delay()` instead computes an exact timestamp difference from the scheduled/actual
+    /// arrival and departure times, and the two can disagree; don't mix the two for the same
+    /// stop.
     pub fn arrival_delay(&self) -> Duration {
         TimeInfo::delay_string_to_duration(&self.arrivalDelay)
     }
 
     /// The departure delay at a stop
+    ///
+    /// Parsed from iceportal's own `+N`/`-N` delay string, which is only minute-granularity.
+    /// `Stop::delay()` instead computes an exact timestamp difference from the scheduled/actual
+    /// arrival and departure times, and the two can disagree; don't mix the two for the same
+    /// stop.
     pub fn departure_delay(&self) -> Duration {
         TimeInfo::delay_string_to_duration(&self.departureDelay)
     }
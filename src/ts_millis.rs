@@ -0,0 +1,35 @@
+//! A decodable newtype for the epoch-millisecond timestamps used throughout the iceportal.de
+//! JSON responses, so the `/1000`/`%1000` conversion math only has to be written once.
+
+extern crate time;
+extern crate rustc_serialize;
+
+use time::{Timespec, Tm};
+use rustc_serialize::{Decodable, Decoder};
+
+/// An epoch-millisecond timestamp, as reported by fields like `serverTime` and every timetable
+/// timestamp.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TsMillis(Timespec);
+
+impl TsMillis {
+    /// The underlying point in time.
+    pub fn tm(&self) -> Tm {
+        time::at(self.0)
+    }
+}
+
+impl Decodable for TsMillis {
+    fn decode<D: Decoder>(d: &mut D) -> Result<TsMillis, D::Error> {
+        let millis = try!(d.read_i64());
+
+        if millis < 0 {
+            return Err(d.error("epoch-millisecond timestamp must not be negative"));
+        }
+
+        Ok(TsMillis(Timespec {
+            sec: millis / 1000,
+            nsec: (millis % 1000) as i32 * 1_000_000,
+        }))
+    }
+}
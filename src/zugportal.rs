@@ -0,0 +1,275 @@
+//! Decoding support for the zugportal.de on-board API used by S-Bahn and regional trains.
+//!
+//! Unlike iceportal.de, zugportal.de reports times as RFC3339 timestamps (a `target` and an
+//! optional `predicted` time) rather than epoch-millis fields plus a separate `+N`/`-N` delay
+//! string, so delays here are derived from the difference between the two instead of parsed
+//! from a string.
+
+extern crate time;
+
+use time::{Duration, Tm};
+use on_board_info::{OnBoardInfo, OnBoardStatus, OnBoardStop};
+use Coordinates;
+
+/// Parses a zugportal.de RFC3339 timestamp.
+fn parse_rfc3339(timestamp: &str) -> Option<Tm> {
+    time::strptime(timestamp, "%Y-%m-%dT%H:%M:%S%z").ok()
+}
+
+/// The station referenced by a `JourneyStop`.
+#[derive(RustcDecodable, Debug)]
+#[allow(non_snake_case)]
+pub struct Station {
+    evaNo: String,
+    name: String,
+}
+
+impl Station {
+    /// The station ID.
+    pub fn eva_no(&self) -> &str {
+        &self.evaNo
+    }
+
+    /// The human-readable station name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// The scheduled and predicted track at a `JourneyStop`.
+#[derive(RustcDecodable, Debug)]
+pub struct Track {
+    target: String,
+    prediction: Option<String>,
+}
+
+impl Track {
+    /// The scheduled track.
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// The predicted track, if it differs from the scheduled one.
+    pub fn prediction(&self) -> Option<&str> {
+        self.prediction.as_ref().map(|track| track.as_str())
+    }
+}
+
+/// A single scheduled/predicted point in time, as reported by zugportal.de.
+#[derive(RustcDecodable, Debug)]
+#[allow(non_snake_case)]
+pub struct TimePoint {
+    target: String,
+    predicted: Option<String>,
+    timeType: Option<String>,
+}
+
+impl TimePoint {
+    /// The scheduled point in time.
+    pub fn target(&self) -> Option<Tm> {
+        parse_rfc3339(&self.target)
+    }
+
+    /// The predicted (possibly delayed) point in time, if already known.
+    pub fn predicted(&self) -> Option<Tm> {
+        self.predicted.as_ref().and_then(|timestamp| parse_rfc3339(timestamp))
+    }
+
+    /// The type of this time point, e.g. "REAL" or "TARGET", if zugportal.de reports one.
+    pub fn time_type(&self) -> Option<&str> {
+        self.timeType.as_ref().map(|time_type| time_type.as_str())
+    }
+}
+
+/// A single stop in a zugportal.de `Journey`.
+#[derive(RustcDecodable, Debug)]
+#[allow(non_snake_case)]
+pub struct JourneyStop {
+    station: Station,
+    status: String,
+    track: Track,
+    messages: Vec<String>,
+    arrivalTime: Option<TimePoint>,
+    departureTime: Option<TimePoint>,
+}
+
+impl JourneyStop {
+    /// The station of this stop.
+    pub fn station(&self) -> &Station {
+        &self.station
+    }
+
+    /// The reported status of this stop, e.g. "Normal".
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    /// The scheduled and predicted track.
+    pub fn track(&self) -> &Track {
+        &self.track
+    }
+
+    /// Free-text messages attached to this stop, such as disruption notices.
+    pub fn messages(&self) -> &[String] {
+        &self.messages
+    }
+}
+
+/// The current GPS/speed status of a train, as reported by the zugportal.de on-board API.
+///
+/// Unlike iceportal.de's `status::Status`, which reports its server time as an epoch-millis
+/// field, zugportal.de reports an RFC3339 timestamp, mirroring the stop-level time handling in
+/// `TimePoint`.
+#[derive(RustcDecodable, Debug)]
+pub struct Status {
+    latitude: f32,
+    longitude: f32,
+    speed: f32,
+    time: String,
+}
+
+impl Status {
+    /// The current speed of the train, in km/h.
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// The current GPS coordinates of the train.
+    pub fn coordinates(&self) -> Coordinates {
+        Coordinates {
+            latitude: self.latitude,
+            longitude: self.longitude,
+        }
+    }
+
+    /// The server time the status was reported at, if it could be parsed.
+    pub fn server_time(&self) -> Option<Tm> {
+        parse_rfc3339(&self.time)
+    }
+}
+
+impl OnBoardStatus for Status {
+    fn speed(&self) -> f32 {
+        Status::speed(self)
+    }
+
+    fn coordinates(&self) -> Coordinates {
+        Status::coordinates(self)
+    }
+
+    fn server_time(&self) -> Option<Tm> {
+        Status::server_time(self)
+    }
+}
+
+/// A train journey as reported by the zugportal.de on-board API.
+///
+/// This is the result of decoding a zugportal.de journey JSON file, whose shape differs
+/// substantially from the iceportal.de `tripInfo` response: the top-level object carries a
+/// line label and train number directly, and each stop nests its own station, track and time
+/// information rather than sharing a single trajectory-wide `stopInfo`.
+#[derive(RustcDecodable, Debug)]
+pub struct Journey {
+    name: String,
+    no: String,
+    stops: Vec<JourneyStop>,
+}
+
+impl Journey {
+    /// The line label of this journey, e.g. "S 8".
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The train number of this journey.
+    pub fn number(&self) -> &str {
+        &self.no
+    }
+}
+
+/// The delay between a scheduled and an actual point in time, if both are known.
+fn delay_between(scheduled: Option<Tm>, actual: Option<Tm>) -> Option<Duration> {
+    match (scheduled, actual) {
+        (Some(scheduled), Some(actual)) => Some(actual - scheduled),
+        _ => None,
+    }
+}
+
+impl OnBoardStop for JourneyStop {
+    fn station_eva(&self) -> &str {
+        self.station().eva_no()
+    }
+
+    fn station_name(&self) -> &str {
+        self.station().name()
+    }
+
+    fn scheduled_arrival(&self) -> Option<Tm> {
+        self.arrivalTime.as_ref().and_then(|t| t.target())
+    }
+
+    fn actual_arrival(&self) -> Option<Tm> {
+        self.arrivalTime.as_ref().and_then(|t| t.predicted().or_else(|| t.target()))
+    }
+
+    fn scheduled_departure(&self) -> Option<Tm> {
+        self.departureTime.as_ref().and_then(|t| t.target())
+    }
+
+    fn actual_departure(&self) -> Option<Tm> {
+        self.departureTime.as_ref().and_then(|t| t.predicted().or_else(|| t.target()))
+    }
+
+    fn arrival_delay(&self) -> Option<Duration> {
+        self.arrivalTime.as_ref().and_then(|t| delay_between(t.target(), t.predicted()))
+    }
+
+    fn departure_delay(&self) -> Option<Duration> {
+        self.departureTime.as_ref().and_then(|t| delay_between(t.target(), t.predicted()))
+    }
+
+    fn passed(&self) -> bool {
+        // zugportal.de does not report a boolean "passed" flag the way iceportal.de does, and
+        // the `status` string's possible values aren't documented, so we can't key off it
+        // either. Instead, treat a stop as passed once its actual (falling back to scheduled)
+        // departure, or arrival if it has no departure, lies in the past.
+        let reference = self.actual_departure()
+            .or_else(|| self.actual_arrival());
+
+        match reference {
+            Some(tm) => tm.to_timespec() < time::now().to_timespec(),
+            None => false,
+        }
+    }
+}
+
+impl OnBoardInfo for Journey {
+    fn train_identifier(&self) -> String {
+        format!("{} {}", self.name, self.no)
+    }
+
+    fn stops(&self) -> Vec<&OnBoardStop> {
+        self.stops.iter().map(|s| s as &OnBoardStop).collect()
+    }
+
+    fn next_stop(&self) -> Option<&OnBoardStop> {
+        self.stops.iter().find(|s| !OnBoardStop::passed(*s)).map(|s| s as &OnBoardStop)
+    }
+
+    fn previous_stop(&self) -> Option<&OnBoardStop> {
+        self.stops.iter().rev().find(|s| OnBoardStop::passed(*s)).map(|s| s as &OnBoardStop)
+    }
+
+    fn origin(&self) -> Option<&OnBoardStop> {
+        self.stops.first().map(|s| s as &OnBoardStop)
+    }
+
+    fn destination(&self) -> Option<&OnBoardStop> {
+        self.stops.last().map(|s| s as &OnBoardStop)
+    }
+
+    fn total_distance(&self) -> f64 {
+        // zugportal.de does not expose a trajectory distance.
+        0f64
+    }
+}